@@ -0,0 +1,255 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use win_hotkeys::VKey;
+
+/// Action-name -> accelerator-string bindings, loaded from `vimbrowse.toml`
+/// next to the executable. Any action missing from the file falls back to
+/// its built-in default.
+#[derive(Clone, Deserialize)]
+pub struct Config {
+    #[serde(default = "default_top")]
+    pub top: String,
+    #[serde(default = "default_bottom")]
+    pub bottom: String,
+    #[serde(default = "default_close_tab")]
+    pub close_tab: String,
+    #[serde(default = "default_new_tab")]
+    pub new_tab: String,
+    #[serde(default = "default_scroll_up")]
+    pub scroll_up: String,
+    #[serde(default = "default_scroll_down")]
+    pub scroll_down: String,
+    #[serde(default = "default_prev_tab")]
+    pub prev_tab: String,
+    #[serde(default = "default_next_tab")]
+    pub next_tab: String,
+    #[serde(default = "default_refresh")]
+    pub refresh: String,
+    #[serde(default = "default_toggle")]
+    pub toggle: String,
+    #[serde(default = "default_scroll_acceleration")]
+    pub scroll_acceleration: f64,
+    #[serde(default = "default_scroll_max_velocity")]
+    pub scroll_max_velocity: f64,
+    #[serde(default = "default_scroll_friction")]
+    pub scroll_friction: f64,
+    #[serde(default = "default_scroll_use_mouse_wheel")]
+    pub scroll_use_mouse_wheel: bool,
+}
+
+fn default_top() -> String {
+    "Q".into()
+}
+
+fn default_bottom() -> String {
+    "E".into()
+}
+
+fn default_close_tab() -> String {
+    "X".into()
+}
+
+fn default_new_tab() -> String {
+    "T".into()
+}
+
+fn default_scroll_up() -> String {
+    "W".into()
+}
+
+fn default_scroll_down() -> String {
+    "S".into()
+}
+
+fn default_prev_tab() -> String {
+    "A".into()
+}
+
+fn default_next_tab() -> String {
+    "D".into()
+}
+
+fn default_refresh() -> String {
+    "R".into()
+}
+
+fn default_toggle() -> String {
+    "Win+Shift+F23".into()
+}
+
+/// Velocity gained per scroll tick (~60 Hz) while `J`/`K` is held.
+fn default_scroll_acceleration() -> f64 {
+    2.0
+}
+
+/// Cap on the scroll velocity, in wheel notches per tick.
+fn default_scroll_max_velocity() -> f64 {
+    20.0
+}
+
+/// Fraction of velocity lost per tick once `J`/`K` is released.
+fn default_scroll_friction() -> f64 {
+    0.12
+}
+
+/// Emit mouse-wheel ticks for kinetic scrolling; set to `false` to fall
+/// back to repeated arrow-key presses on pages that ignore wheel events.
+fn default_scroll_use_mouse_wheel() -> bool {
+    true
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            top: default_top(),
+            bottom: default_bottom(),
+            close_tab: default_close_tab(),
+            new_tab: default_new_tab(),
+            scroll_up: default_scroll_up(),
+            scroll_down: default_scroll_down(),
+            prev_tab: default_prev_tab(),
+            next_tab: default_next_tab(),
+            refresh: default_refresh(),
+            toggle: default_toggle(),
+            scroll_acceleration: default_scroll_acceleration(),
+            scroll_max_velocity: default_scroll_max_velocity(),
+            scroll_friction: default_scroll_friction(),
+            scroll_use_mouse_wheel: default_scroll_use_mouse_wheel(),
+        }
+    }
+}
+
+/// Load `vimbrowse.toml` from beside the running executable, falling back
+/// to the built-in defaults when it is absent or malformed.
+pub fn load_config() -> Config {
+    config_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|contents| toml::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn config_path() -> Option<PathBuf> {
+    let mut path = std::env::current_exe().ok()?;
+    path.set_file_name("vimbrowse.toml");
+    Some(path)
+}
+
+/// Parse an accelerator string such as `"Ctrl+Shift+Tab"` into its
+/// modifier keys and main key. The last `+`-separated token is the main
+/// key; every token before it is a modifier.
+pub fn parse_accelerator(accelerator: &str) -> Result<(Vec<VKey>, VKey)> {
+    let mut tokens = accelerator.split('+').map(str::trim).peekable();
+    let mut modifiers = Vec::new();
+    let mut main_key = None;
+
+    while let Some(token) = tokens.next() {
+        if tokens.peek().is_some() {
+            modifiers.push(token_to_vkey(token)?);
+        } else {
+            main_key = Some(token_to_vkey(token)?);
+        }
+    }
+
+    main_key
+        .with_context(|| format!("empty accelerator: {accelerator:?}"))
+        .map(|key| (modifiers, key))
+}
+
+fn token_to_vkey(token: &str) -> Result<VKey> {
+    if let Some(vkey) = single_char_vkey(token) {
+        return Ok(vkey);
+    }
+
+    Ok(match token {
+        "Ctrl" | "Control" => VKey::Control,
+        "Shift" => VKey::Shift,
+        "Alt" => VKey::Alt,
+        "Win" => VKey::LWin,
+        "Space" => VKey::Space,
+        "Tab" => VKey::Tab,
+        "," => VKey::OemComma,
+        "-" => VKey::OemMinus,
+        "." => VKey::OemPeriod,
+        "=" => VKey::OemPlus,
+        ";" => VKey::OemSemicolon,
+        "/" => VKey::OemQuestion,
+        "\\" => VKey::OemPipe,
+        "`" => VKey::OemTilde,
+        "[" => VKey::OemOpenBrackets,
+        "]" => VKey::OemCloseBrackets,
+        "F1" => VKey::F1,
+        "F2" => VKey::F2,
+        "F3" => VKey::F3,
+        "F4" => VKey::F4,
+        "F5" => VKey::F5,
+        "F6" => VKey::F6,
+        "F7" => VKey::F7,
+        "F8" => VKey::F8,
+        "F9" => VKey::F9,
+        "F10" => VKey::F10,
+        "F11" => VKey::F11,
+        "F12" => VKey::F12,
+        "F13" => VKey::F13,
+        "F14" => VKey::F14,
+        "F15" => VKey::F15,
+        "F16" => VKey::F16,
+        "F17" => VKey::F17,
+        "F18" => VKey::F18,
+        "F19" => VKey::F19,
+        "F20" => VKey::F20,
+        "F21" => VKey::F21,
+        "F22" => VKey::F22,
+        "F23" => VKey::F23,
+        "F24" => VKey::F24,
+        other => bail!("unknown accelerator key: {other:?}"),
+    })
+}
+
+fn single_char_vkey(token: &str) -> Option<VKey> {
+    if token.chars().count() != 1 {
+        return None;
+    }
+
+    let ch = token.chars().next()?.to_ascii_uppercase();
+    Some(match ch {
+        'A' => VKey::A,
+        'B' => VKey::B,
+        'C' => VKey::C,
+        'D' => VKey::D,
+        'E' => VKey::E,
+        'F' => VKey::F,
+        'G' => VKey::G,
+        'H' => VKey::H,
+        'I' => VKey::I,
+        'J' => VKey::J,
+        'K' => VKey::K,
+        'L' => VKey::L,
+        'M' => VKey::M,
+        'N' => VKey::N,
+        'O' => VKey::O,
+        'P' => VKey::P,
+        'Q' => VKey::Q,
+        'R' => VKey::R,
+        'S' => VKey::S,
+        'T' => VKey::T,
+        'U' => VKey::U,
+        'V' => VKey::V,
+        'W' => VKey::W,
+        'X' => VKey::X,
+        'Y' => VKey::Y,
+        'Z' => VKey::Z,
+        '0' => VKey::Key0,
+        '1' => VKey::Key1,
+        '2' => VKey::Key2,
+        '3' => VKey::Key3,
+        '4' => VKey::Key4,
+        '5' => VKey::Key5,
+        '6' => VKey::Key6,
+        '7' => VKey::Key7,
+        '8' => VKey::Key8,
+        '9' => VKey::Key9,
+        _ => return None,
+    })
+}
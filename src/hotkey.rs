@@ -1,7 +1,28 @@
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, mpsc};
+use std::time::Duration;
+
+use anyhow::Result;
+use win_hotkeys::{HotkeyManager, InterruptHandle};
+use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+use windows::Win32::System::Power::{PBT_APMRESUMEAUTOMATIC, PBT_APMRESUMESUSPEND};
+use windows::Win32::System::RemoteDesktop::{
+    NOTIFY_FOR_THIS_SESSION, WTSRegisterSessionNotification,
+};
 use windows::Win32::UI::Input::KeyboardAndMouse::{
-    INPUT, INPUT_0, INPUT_KEYBOARD, INPUT_MOUSE, KEYBD_EVENT_FLAGS, KEYBDINPUT, KEYEVENTF_KEYUP,
-    MOUSEEVENTF_WHEEL, MOUSEINPUT, SendInput, VIRTUAL_KEY,
+    GetAsyncKeyState, INPUT, INPUT_0, INPUT_KEYBOARD, INPUT_MOUSE, KEYBD_EVENT_FLAGS, KEYBDINPUT,
+    KEYEVENTF_KEYUP, MOUSEEVENTF_WHEEL, MOUSEINPUT, SendInput, VIRTUAL_KEY, VK_DOWN, VK_J, VK_K,
+    VK_UP,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    CREATESTRUCTW, CreateWindowExW, DefWindowProcW, DispatchMessageW, GWLP_USERDATA, GetMessageW,
+    GetWindowLongPtrW, HWND_MESSAGE, MSG, RegisterClassExW, SetWindowLongPtrW, TranslateMessage,
+    WINDOW_EX_STYLE, WM_CREATE, WM_POWERBROADCAST, WM_WTSSESSION_CHANGE, WNDCLASSEXW,
+    WS_OVERLAPPED,
 };
+use windows::core::w;
 
 pub fn send_keys(inputs: &[VIRTUAL_KEY]) {
     let keys = inputs
@@ -31,6 +52,34 @@ pub fn send_keys(inputs: &[VIRTUAL_KEY]) {
     });
 }
 
+/// Press and release `key` `count` times in a single `SendInput` call,
+/// rather than spawning a thread per press — used by the momentum-scroll
+/// fallback, which can otherwise ask for dozens of presses per tick.
+pub fn send_keys_repeated(key: VIRTUAL_KEY, count: u32) {
+    let mut inputs: Vec<INPUT> = Vec::with_capacity(count as usize * 2);
+
+    for _ in 0..count {
+        for state in [KEYBD_EVENT_FLAGS(0), KEYEVENTF_KEYUP] {
+            inputs.push(INPUT {
+                r#type: INPUT_KEYBOARD,
+                Anonymous: INPUT_0 {
+                    ki: KEYBDINPUT {
+                        wVk: key,
+                        wScan: 0,
+                        dwFlags: state,
+                        time: 0,
+                        dwExtraInfo: 0,
+                    },
+                },
+            });
+        }
+    }
+
+    std::thread::spawn(move || unsafe {
+        SendInput(&inputs, std::mem::size_of::<INPUT>() as i32);
+    });
+}
+
 pub fn send_mouse_scoll(delta: i32) {
     let input = INPUT {
         r#type: INPUT_MOUSE,
@@ -50,3 +99,334 @@ pub fn send_mouse_scoll(delta: i32) {
         SendInput(&[input], std::mem::size_of::<INPUT>() as i32);
     });
 }
+
+type ResumeCallback = Box<dyn Fn() + Send>;
+
+/// Keeps the hotkey hook alive across sleep/wake and session changes.
+///
+/// Windows silently tears down `win_hotkeys`'s low-level keyboard hook
+/// after the machine resumes from suspend, which otherwise leaves
+/// VimBrowse's hotkeys dead until the process is restarted. This watches
+/// for `WM_POWERBROADCAST` resume notifications (and session-change
+/// notifications, for the same symptom after a remote-session reconnect)
+/// on a hidden message-only window and rebuilds the `HotkeyManager` from
+/// scratch by re-running `register` every time one fires.
+pub struct HookSupervisor;
+
+impl HookSupervisor {
+    pub fn spawn(register: impl Fn(&mut HotkeyManager) + Send + Sync + 'static) -> Result<Self> {
+        let register = Arc::new(register);
+        let (restart_tx, restart_rx) = mpsc::channel::<()>();
+        let interrupt_handle: Arc<Mutex<Option<InterruptHandle>>> = Arc::new(Mutex::new(None));
+
+        {
+            let register = Arc::clone(&register);
+            let interrupt_handle = Arc::clone(&interrupt_handle);
+            std::thread::spawn(move || {
+                loop {
+                    let mut hkm = HotkeyManager::new();
+                    register(&mut hkm);
+                    *interrupt_handle.lock().unwrap() = Some(hkm.interrupt_handle());
+
+                    hkm.event_loop();
+
+                    if restart_rx.recv().is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        spawn_power_event_window(move || {
+            if let Some(handle) = interrupt_handle.lock().unwrap().as_ref() {
+                handle.stop();
+            }
+            let _ = restart_tx.send(());
+        })?;
+
+        Ok(HookSupervisor)
+    }
+}
+
+fn spawn_power_event_window(on_resume: impl Fn() + Send + 'static) -> Result<()> {
+    let callback: *mut ResumeCallback = Box::into_raw(Box::new(Box::new(on_resume)));
+
+    std::thread::spawn(move || unsafe {
+        let class_name = w!("VimBrowsePowerEventWindow");
+
+        let wc = WNDCLASSEXW {
+            cbSize: std::mem::size_of::<WNDCLASSEXW>() as u32,
+            lpfnWndProc: Some(power_event_wndproc),
+            lpszClassName: class_name,
+            ..Default::default()
+        };
+        RegisterClassExW(&wc);
+
+        let hwnd = CreateWindowExW(
+            WINDOW_EX_STYLE::default(),
+            class_name,
+            w!("VimBrowsePowerEvents"),
+            WS_OVERLAPPED,
+            0,
+            0,
+            0,
+            0,
+            Some(HWND_MESSAGE),
+            None,
+            None,
+            Some(callback as *const c_void),
+        )
+        .expect("failed to create the power-event message-only window");
+
+        let _ = WTSRegisterSessionNotification(hwnd, NOTIFY_FOR_THIS_SESSION);
+
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+    });
+
+    Ok(())
+}
+
+unsafe extern "system" fn power_event_wndproc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    unsafe {
+        match msg {
+            WM_CREATE => {
+                let create_struct = lparam.0 as *const CREATESTRUCTW;
+                let callback = (*create_struct).lpCreateParams as isize;
+                SetWindowLongPtrW(hwnd, GWLP_USERDATA, callback);
+            }
+            WM_POWERBROADCAST
+                if wparam.0 as u32 == PBT_APMRESUMEAUTOMATIC.0
+                    || wparam.0 as u32 == PBT_APMRESUMESUSPEND.0 =>
+            {
+                notify_resume(hwnd);
+            }
+            WM_WTSSESSION_CHANGE => {
+                notify_resume(hwnd);
+            }
+            _ => {}
+        }
+
+        DefWindowProcW(hwnd, msg, wparam, lparam)
+    }
+}
+
+unsafe fn notify_resume(hwnd: HWND) {
+    unsafe {
+        let callback = GetWindowLongPtrW(hwnd, GWLP_USERDATA) as *const ResumeCallback;
+        if let Some(callback) = callback.as_ref() {
+            callback();
+        }
+    }
+}
+
+type CommandAction = Arc<dyn Fn() + Send + Sync>;
+
+#[derive(Default)]
+struct ParserState {
+    count: Option<u32>,
+    buffer: String,
+}
+
+enum KeyOutcome {
+    Dispatch(CommandAction, u32),
+    Invalid,
+    Pending,
+}
+
+/// A Vim-style "count + multi-key command" input buffer, e.g. `5j` or `gg`.
+///
+/// Digits feed into a pending repeat count; letters feed into a buffer
+/// matched against the bound commands. A short inter-key timeout
+/// resolves an ambiguous prefix (`g` could begin both `gg` and `gt`, so
+/// it waits for the next key) and discards a dangling count or an
+/// unmatched sequence once it goes stale.
+pub struct CommandParser {
+    commands: HashMap<String, CommandAction>,
+    timeout: Duration,
+    state: Mutex<ParserState>,
+    generation: AtomicU64,
+}
+
+impl CommandParser {
+    pub fn new(timeout: Duration) -> Arc<Self> {
+        Arc::new(CommandParser {
+            commands: HashMap::new(),
+            timeout,
+            state: Mutex::new(ParserState::default()),
+            generation: AtomicU64::new(0),
+        })
+    }
+
+    /// Bind a key sequence, e.g. `"gg"` or `"j"`, to an action. The
+    /// action is invoked once per repeat of the accumulated count
+    /// (defaulting to 1) once the sequence resolves.
+    pub fn bind(self: &mut Arc<Self>, sequence: &str, action: impl Fn() + Send + Sync + 'static) {
+        Arc::get_mut(self)
+            .expect("CommandParser::bind must run before the parser is shared")
+            .commands
+            .insert(sequence.to_string(), Arc::new(action));
+    }
+
+    /// Feed a single digit (`0`-`9`) into the pending repeat count.
+    pub fn feed_digit(self: &Arc<Self>, digit: u32) {
+        {
+            let mut state = self.state.lock().unwrap();
+            state.count = Some(state.count.unwrap_or(0) * 10 + digit);
+        }
+        self.arm_timeout();
+    }
+
+    /// Feed a single command-sequence key, e.g. `'g'` then `'g'` for `gg`.
+    pub fn feed_key(self: &Arc<Self>, ch: char) {
+        let outcome = {
+            let mut state = self.state.lock().unwrap();
+            state.buffer.push(ch);
+
+            let ambiguous = self.commands.keys().any(|seq| {
+                seq.len() > state.buffer.len() && seq.starts_with(state.buffer.as_str())
+            });
+
+            if ambiguous {
+                KeyOutcome::Pending
+            } else if let Some(action) = self.commands.get(&state.buffer).cloned() {
+                let count = state.count.take().unwrap_or(1);
+                state.buffer.clear();
+                KeyOutcome::Dispatch(action, count)
+            } else {
+                state.buffer.clear();
+                state.count = None;
+                KeyOutcome::Invalid
+            }
+        };
+
+        match outcome {
+            KeyOutcome::Dispatch(action, count) => {
+                self.generation.fetch_add(1, Ordering::SeqCst);
+                for _ in 0..count {
+                    action();
+                }
+            }
+            KeyOutcome::Invalid => {
+                self.generation.fetch_add(1, Ordering::SeqCst);
+            }
+            KeyOutcome::Pending => self.arm_timeout(),
+        }
+    }
+
+    /// Discard any pending count or partial sequence, e.g. on `Esc`.
+    pub fn reset(self: &Arc<Self>) {
+        let mut state = self.state.lock().unwrap();
+        state.buffer.clear();
+        state.count = None;
+        drop(state);
+        self.generation.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn arm_timeout(self: &Arc<Self>) {
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let parser = Arc::clone(self);
+        std::thread::spawn(move || {
+            std::thread::sleep(parser.timeout);
+            parser.resolve_on_timeout(generation);
+        });
+    }
+
+    fn resolve_on_timeout(&self, generation: u64) {
+        // A newer key arrived before this timeout fired; let it own the buffer.
+        if self.generation.load(Ordering::SeqCst) != generation {
+            return;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        if let Some(action) = self.commands.get(&state.buffer).cloned() {
+            let count = state.count.take().unwrap_or(1);
+            state.buffer.clear();
+            drop(state);
+            for _ in 0..count {
+                action();
+            }
+        } else {
+            // Dangling count or an unresolved prefix: discard it.
+            state.buffer.clear();
+            state.count = None;
+        }
+    }
+}
+
+/// Tuning knobs for the momentum ("kinetic") scroll mode driven by
+/// holding `J`/`K`, mirrored from [`crate::config::Config`].
+pub struct ScrollConfig {
+    pub acceleration: f64,
+    pub max_velocity: f64,
+    pub friction: f64,
+    pub use_mouse_wheel: bool,
+    /// The toggle hotkey's `show_state`: momentum scroll only emits input
+    /// while this is `true`, matching every other action's pause behaviour.
+    pub active: Arc<AtomicBool>,
+}
+
+const SCROLL_TICK: Duration = Duration::from_millis(16);
+
+fn key_held(vkey: VIRTUAL_KEY) -> bool {
+    unsafe { GetAsyncKeyState(vkey.0 as i32) as u16 & 0x8000 != 0 }
+}
+
+/// Spawn the background tick that turns holding `J`/`K` into inertial,
+/// trackpad-like scrolling: velocity ramps up by `acceleration` per tick
+/// while the key is held, decays by `friction` once released, and is
+/// emitted each tick as a `send_mouse_scoll` delta (or, when
+/// `use_mouse_wheel` is disabled, as a single batched key-repeat via
+/// `send_keys_repeated`, for pages that ignore wheel events). Emission
+/// and velocity both stop dead while `config.active` is cleared,
+/// mirroring every other action's behaviour under the toggle hotkey.
+pub fn spawn_momentum_scroll(config: ScrollConfig) {
+    std::thread::spawn(move || {
+        let mut velocity: f64 = 0.0;
+
+        loop {
+            std::thread::sleep(SCROLL_TICK);
+
+            if !config.active.load(Ordering::Relaxed) {
+                velocity = 0.0;
+                continue;
+            }
+
+            let direction = match (key_held(VK_J), key_held(VK_K)) {
+                (true, false) => 1.0,
+                (false, true) => -1.0,
+                _ => 0.0,
+            };
+
+            if direction != 0.0 {
+                velocity = (velocity + direction * config.acceleration)
+                    .clamp(-config.max_velocity, config.max_velocity);
+            } else if velocity != 0.0 {
+                velocity *= 1.0 - config.friction;
+                if velocity.abs() < 0.1 {
+                    velocity = 0.0;
+                }
+            }
+
+            let delta = velocity.round() as i32;
+            if delta == 0 {
+                continue;
+            }
+
+            if config.use_mouse_wheel {
+                send_mouse_scoll(-delta);
+            } else {
+                let step = if delta > 0 { VK_DOWN } else { VK_UP };
+                send_keys_repeated(step, delta.unsigned_abs());
+            }
+        }
+    });
+}
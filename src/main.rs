@@ -1,11 +1,13 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod config;
 mod monitor;
 mod uiaccess;
 
-use hotkey::send_keys;
+use config::{load_config, parse_accelerator};
+use hotkey::{CommandParser, ScrollConfig, send_keys, spawn_momentum_scroll};
 use hsv::hsv_to_rgb;
-use monitor::get_primary_monitor_logical_size;
+use monitor::{MonitorInfo, enumerate_monitors};
 use uiaccess::prepare_uiaccess_token;
 mod hotkey;
 
@@ -15,7 +17,6 @@ use std::sync::{
     Arc,
     atomic::{AtomicBool, Ordering},
 };
-use std::thread::sleep;
 use std::time::{Duration, Instant};
 
 use anyhow::Result;
@@ -35,90 +36,109 @@ use winit::{
 
 const SPEED: f64 = 0.1;
 
-struct App {
-    window: Option<Rc<Window>>,
-    surface: Option<Surface<Rc<Window>, Rc<Window>>>,
-    time: Instant,
+/// The transparent always-on-top border overlay for a single monitor.
+struct Overlay {
+    window: Rc<Window>,
+    surface: Surface<Rc<Window>, Rc<Window>>,
     last_window_size: (u32, u32),
     border_width: u32,
     perimeter: f64,
+}
+
+impl Overlay {
+    fn new(event_loop: &ActiveEventLoop, monitor: &MonitorInfo) -> Self {
+        let window = event_loop
+            .create_window(
+                Window::default_attributes()
+                    .with_title("VimBrowse")
+                    .with_skip_taskbar(!cfg!(debug_assertions))
+                    .with_undecorated_shadow(cfg!(debug_assertions))
+                    .with_content_protected(!cfg!(debug_assertions))
+                    .with_decorations(false)
+                    .with_window_level(WindowLevel::AlwaysOnTop)
+                    .with_transparent(true)
+                    .with_inner_size(PhysicalSize::new(monitor.size.0, monitor.size.1))
+                    .with_position(PhysicalPosition::new(
+                        monitor.position.0,
+                        monitor.position.1,
+                    ))
+                    .with_active(false)
+                    .with_resizable(false),
+            )
+            .unwrap();
+
+        window.set_enable(false);
+        window.set_cursor_hittest(false).unwrap();
+        window.request_redraw();
+
+        let window = Rc::new(window);
+        let context = softbuffer::Context::new(window.clone())
+            .expect("Failed to create a new instance of context - {e}");
+        let mut surface = softbuffer::Surface::new(&context, window.clone())
+            .expect("Failed to create a surface for drawing to window - {e}");
+
+        let (width, height): (u32, u32) = window.inner_size().into();
+
+        surface
+            .resize(
+                NonZeroU32::new(width).unwrap(),
+                NonZeroU32::new(height).unwrap(),
+            )
+            .expect("Failed to set the size of the buffer");
+
+        let mut buffer = surface.buffer_mut().unwrap();
+        buffer.fill(0);
+        buffer.present().unwrap();
+
+        Overlay {
+            window,
+            surface,
+            last_window_size: (width, height),
+            border_width: (4.0 * monitor.scale_factor).round() as u32,
+            perimeter: 0.0,
+        }
+    }
+}
+
+struct App {
+    overlays: Vec<Overlay>,
+    time: Instant,
     show_state: Arc<AtomicBool>,
 }
 
 impl App {
-    fn create_window(&mut self, event_loop: &ActiveEventLoop) {
+    fn create_windows(&mut self, event_loop: &ActiveEventLoop) {
         let show_state = self.show_state.load(Ordering::Relaxed);
-        let (monitor_width, monitor_height) = get_primary_monitor_logical_size().unwrap();
 
         if !show_state {
-            if let Some(window) = self.window.take() {
-                window.set_visible(false);
-                self.window = None;
-                self.surface = None;
+            for overlay in self.overlays.drain(..) {
+                overlay.window.set_visible(false);
             }
             return;
         }
 
-        if self.window.is_none() {
-            let window = event_loop
-                .create_window(
-                    Window::default_attributes()
-                        .with_title("VimBrowse")
-                        .with_skip_taskbar(!cfg!(debug_assertions))
-                        .with_undecorated_shadow(cfg!(debug_assertions))
-                        .with_content_protected(!cfg!(debug_assertions))
-                        .with_decorations(false)
-                        .with_window_level(WindowLevel::AlwaysOnTop)
-                        .with_transparent(true)
-                        .with_inner_size(PhysicalSize::new(monitor_width, monitor_height))
-                        .with_position(PhysicalPosition::new(0, 0))
-                        .with_active(false)
-                        .with_resizable(false),
-                )
-                .unwrap();
-
-            window.set_enable(false);
-            window.set_cursor_hittest(false).unwrap();
-            window.request_redraw();
-
-            let (window, _context, mut surface) = {
-                let window = Rc::new(window);
-                let context = softbuffer::Context::new(window.clone())
-                    .expect("Failed to create a new instance of context - {e}");
-                let surface = softbuffer::Surface::new(&context, window.clone())
-                    .expect("Failed to create a surface for drawing to window - {e}");
-                (window, context, surface)
-            };
-
-            let (width, height): (u32, u32) = window.inner_size().into();
-
-            surface
-                .resize(
-                    NonZeroU32::new(width).unwrap(),
-                    NonZeroU32::new(height).unwrap(),
-                )
-                .expect("Failed to set the size of the buffer");
-
-            let mut buffer = surface.buffer_mut().unwrap();
-
-            buffer.fill(0);
-            buffer.present().unwrap();
-
-            self.window = Some(window);
-            self.surface = Some(surface);
+        if self.overlays.is_empty() {
+            let monitors = enumerate_monitors().unwrap();
+            self.overlays = monitors
+                .iter()
+                .map(|monitor| Overlay::new(event_loop, monitor))
+                .collect();
         }
     }
 }
 
 impl ApplicationHandler for App {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
-        self.create_window(event_loop)
+        self.create_windows(event_loop)
     }
 
     fn window_event(&mut self, event_loop: &ActiveEventLoop, id: WindowId, event: WindowEvent) {
-        let window = match self.window.as_ref().filter(|w| w.id() == id) {
-            Some(w) => w,
-            None => return,
+        let Some(overlay) = self
+            .overlays
+            .iter_mut()
+            .find(|overlay| overlay.window.id() == id)
+        else {
+            return;
         };
 
         match event {
@@ -126,85 +146,106 @@ impl ApplicationHandler for App {
                 event_loop.exit();
             }
             WindowEvent::RedrawRequested => {
-                sleep(Duration::from_millis(60));
-
                 if !self.show_state.load(Ordering::Relaxed) {
                     return;
                 }
 
                 let (width, height) = {
-                    let size = window.inner_size();
+                    let size = overlay.window.inner_size();
                     (size.width, size.height)
                 };
 
                 // 更新图形资源
-                let surface = self.surface.as_mut().unwrap();
-                if self.last_window_size != (width, height) {
-                    surface
+                let resized = overlay.last_window_size != (width, height);
+                if resized {
+                    overlay
+                        .surface
                         .resize(
                             NonZeroU32::new(width).unwrap(),
                             NonZeroU32::new(height).unwrap(),
                         )
                         .unwrap();
-                    self.last_window_size = (width, height);
+                    overlay.last_window_size = (width, height);
                 }
 
                 // 更新边框参数
-                let scale_factor = window.scale_factor();
-                self.border_width = (4.0 * scale_factor).round() as u32;
-                self.perimeter =
-                    2.0 * (width as f64 + height as f64 - 2.0 * self.border_width as f64);
+                let scale_factor = overlay.window.scale_factor();
+                overlay.border_width = (4.0 * scale_factor).round() as u32;
+                overlay.perimeter =
+                    2.0 * (width as f64 + height as f64 - 2.0 * overlay.border_width as f64);
 
                 // 获取绘图缓冲区
-                let mut buffer = surface.buffer_mut().unwrap();
+                let mut buffer = overlay.surface.buffer_mut().unwrap();
                 let buffer_len = (width * height) as usize;
                 if buffer.len() != buffer_len {
                     return;
                 }
 
+                // 创建/缩放后整块清零一次，其余每帧只重绘四条边框带，而非整个帧缓冲
+                if resized {
+                    buffer.fill(0);
+                }
+
                 let elapsed = self.time.elapsed().as_secs_f64();
                 let time_phase = (elapsed * SPEED) % 1.0;
 
                 let buffer_slice = buffer.as_mut();
-                let border_width = self.border_width;
-                let perimeter = self.perimeter;
+                let border_width = overlay.border_width;
+                let perimeter = overlay.perimeter;
 
                 let bottom_y = height - border_width;
                 let right_x = width - border_width;
-                buffer_slice.iter_mut().enumerate().for_each(|(i, pixel)| {
-                    let x = i as u32 % width;
-                    let y = i as u32 / width;
-
-                    let in_top = y < border_width;
-                    let in_bottom = y >= bottom_y;
-                    let in_left = x < border_width;
-                    let in_right = x >= right_x;
-
-                    if in_top || in_bottom || in_left || in_right {
-                        let pos = match () {
-                            _ if in_top => x as f64,
-                            _ if in_right => width as f64 + (y - border_width) as f64,
-                            _ if in_bottom => {
-                                width as f64
-                                    + (height - 2 * border_width) as f64
-                                    + (width - x - 1) as f64
-                            }
-                            _ => {
-                                (2 * width + height - 2 * border_width) as f64
-                                    + (height - y - border_width - 1) as f64
-                            }
-                        } / perimeter;
-
-                        let phase = (pos + time_phase) % 1.0;
-                        let rgb = hsv_to_rgb(phase * 360.0, 1.0, 1.0);
-                        *pixel = ((rgb.0 as u32) << 16) | ((rgb.1 as u32) << 8) | rgb.2 as u32;
+
+                let mut paint = |x: u32, y: u32, pos: f64| {
+                    let phase = (pos / perimeter + time_phase) % 1.0;
+                    let rgb = hsv_to_rgb(phase * 360.0, 1.0, 1.0);
+                    buffer_slice[(y * width + x) as usize] =
+                        ((rgb.0 as u32) << 16) | ((rgb.1 as u32) << 8) | rgb.2 as u32;
+                };
+
+                // 上
+                for y in 0..border_width {
+                    for x in 0..width {
+                        paint(x, y, x as f64);
+                    }
+                }
+
+                // 右（含右下角）
+                for y in border_width..height {
+                    for x in right_x..width {
+                        paint(x, y, width as f64 + (y - border_width) as f64);
                     }
-                });
+                }
+
+                // 下（不含右下角，已由右边带处理）
+                for y in bottom_y..height {
+                    for x in 0..right_x {
+                        paint(
+                            x,
+                            y,
+                            width as f64
+                                + (height - 2 * border_width) as f64
+                                + (width - x - 1) as f64,
+                        );
+                    }
+                }
+
+                // 左
+                for y in border_width..bottom_y {
+                    for x in 0..border_width {
+                        paint(
+                            x,
+                            y,
+                            (2 * width + height - 2 * border_width) as f64
+                                + (height - y - border_width - 1) as f64,
+                        );
+                    }
+                }
 
                 buffer.present().unwrap();
 
                 if self.show_state.load(Ordering::Relaxed) {
-                    window.request_redraw();
+                    overlay.window.request_redraw();
                 }
             }
             _ => (),
@@ -212,7 +253,7 @@ impl ApplicationHandler for App {
     }
 
     fn user_event(&mut self, event_loop: &ActiveEventLoop, _event: ()) {
-        self.create_window(event_loop);
+        self.create_windows(event_loop);
     }
 }
 
@@ -228,12 +269,8 @@ fn main() -> Result<()> {
     std::thread::spawn(move || listen_and_send(show_state_clone, event_loop_proxy));
 
     let mut app = App {
-        window: None,
-        surface: None,
+        overlays: Vec::new(),
         time: Instant::now(),
-        last_window_size: (0, 0),
-        border_width: 4,
-        perimeter: 0.0,
         show_state,
     };
     event_loop.run_app(&mut app).unwrap();
@@ -241,75 +278,175 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn listen_and_send(
-    show_state: Arc<AtomicBool>,
-    event_loop_proxy: winit::event_loop::EventLoopProxy<()>,
+/// Register one action's hotkey from its configured accelerator string,
+/// logging instead of panicking when the accelerator can't be parsed or
+/// the key combination is already taken.
+fn register_action(
+    hkm: &mut HotkeyManager,
+    action: &str,
+    accelerator: &str,
+    callback: impl Fn() + Send + 'static,
 ) {
-    let show_state = Arc::clone(&show_state);
-
-    // 睡眠唤醒后键盘钩子失效，解决办法：重启键盘钩子？
-    let mut hkm = HotkeyManager::new();
-
-    // 返回顶部
-    hkm.register_hotkey(VKey::Q, &[], move || {
-        send_keys(&[VK_CONTROL, VK_HOME]);
-    })
-    .unwrap();
-
-    // 返回底部
-    hkm.register_hotkey(VKey::E, &[], move || {
-        send_keys(&[VK_CONTROL, VK_END]);
-    })
-    .unwrap();
-
-    // 关闭应用内窗口
-    hkm.register_hotkey(VKey::X, &[], move || {
-        send_keys(&[VK_CONTROL, VK_W]);
-    })
-    .unwrap();
-
-    // 创建应用内窗口
-    hkm.register_hotkey(VKey::T, &[], move || {
-        send_keys(&[VK_CONTROL, VK_T]);
-    })
-    .unwrap();
-
-    // 上
-    hkm.register_hotkey(VKey::W, &[], move || {
-        send_keys(&[VK_UP]);
-    })
-    .unwrap();
+    let (modifiers, main_key) = match parse_accelerator(accelerator) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("VimBrowse: invalid accelerator for {action:?} ({accelerator:?}): {e}");
+            return;
+        }
+    };
 
-    // 下
-    hkm.register_hotkey(VKey::S, &[], move || {
-        send_keys(&[VK_DOWN]);
-    })
-    .unwrap();
+    if let Err(e) = hkm.register_hotkey(main_key, &modifiers, callback) {
+        eprintln!("VimBrowse: failed to register {action:?} ({accelerator:?}): {e}");
+    }
+}
 
-    // 切换左标题页
-    hkm.register_hotkey(VKey::A, &[], move || {
-        send_keys(&[VK_CONTROL, VK_SHIFT, VK_TAB, VK_A]);
-    })
-    .unwrap();
+/// Register a bare key (optionally with modifiers) that feeds a single
+/// character into the Vim-style `CommandParser` rather than dispatching
+/// an action directly.
+fn register_parser_key(
+    hkm: &mut HotkeyManager,
+    main_key: VKey,
+    modifiers: &[VKey],
+    parser: &Arc<CommandParser>,
+    feed: impl Fn(&Arc<CommandParser>) + Send + 'static,
+) {
+    let parser = Arc::clone(parser);
+    if let Err(e) = hkm.register_hotkey(main_key, modifiers, move || feed(&parser)) {
+        eprintln!("VimBrowse: failed to register vim key {main_key:?}: {e}");
+    }
+}
 
-    // 切换左标题页
-    hkm.register_hotkey(VKey::D, &[], move || {
-        send_keys(&[VK_CONTROL, VK_TAB, VK_D]);
-    })
-    .unwrap();
+/// Build the Vim-style count/sequence layer: `5j`/`5k` repeat a scroll
+/// step, `gg` jumps to the top, and `gl`/`gh` switch tabs — on top of,
+/// and without disturbing, the single-key accelerator bindings above.
+///
+/// `j`/`k` are fed from the same `J`/`K` keys that drive chunk0-6's
+/// momentum scroll: that poller only looks at live key state via
+/// `GetAsyncKeyState` and never calls `register_hotkey`, so it doesn't
+/// contend with these discrete hotkey registrations for the same key.
+/// Tab-switching deliberately avoids `T`/Shift+T, since `new_tab` is
+/// already (configurably) bound there. `H`/`L` are not among the default
+/// single-key bindings; an action configured onto either key is
+/// registered first, and the vim key's registration then fails with a
+/// logged error.
+fn build_vim_command_parser() -> Arc<CommandParser> {
+    let mut parser = CommandParser::new(Duration::from_millis(600));
+
+    parser.bind("j", || send_keys(&[VK_DOWN]));
+    parser.bind("k", || send_keys(&[VK_UP]));
+    parser.bind("gg", || send_keys(&[VK_CONTROL, VK_HOME]));
+    parser.bind("gl", || send_keys(&[VK_CONTROL, VK_TAB, VK_D]));
+    parser.bind("gh", || send_keys(&[VK_CONTROL, VK_SHIFT, VK_TAB, VK_A]));
+
+    parser
+}
 
-    // 刷新
-    hkm.register_hotkey(VKey::R, &[], move || {
-        send_keys(&[VK_F5]);
-    })
-    .unwrap();
+fn listen_and_send(
+    show_state: Arc<AtomicBool>,
+    event_loop_proxy: winit::event_loop::EventLoopProxy<()>,
+) {
+    let config = load_config();
+    let vim_parser = build_vim_command_parser();
+
+    // 按住 J/K 进行惯性平滑滚动，独立于下面的单键钩子运行，但共享同一个
+    // show_state 暂停标志，暂停后不再发送滚动输入
+    spawn_momentum_scroll(ScrollConfig {
+        acceleration: config.scroll_acceleration,
+        max_velocity: config.scroll_max_velocity,
+        friction: config.scroll_friction,
+        use_mouse_wheel: config.scroll_use_mouse_wheel,
+        active: Arc::clone(&show_state),
+    });
+
+    // 睡眠唤醒后键盘钩子失效，由 HookSupervisor 监听电源/会话事件并重建钩子
+    hotkey::HookSupervisor::spawn(move |hkm| {
+        let show_state = Arc::clone(&show_state);
+        let event_loop_proxy = event_loop_proxy.clone();
+        let config = config.clone();
+
+        // 返回顶部
+        register_action(hkm, "top", &config.top, move || {
+            send_keys(&[VK_CONTROL, VK_HOME]);
+        });
+
+        // 返回底部
+        register_action(hkm, "bottom", &config.bottom, move || {
+            send_keys(&[VK_CONTROL, VK_END]);
+        });
+
+        // 关闭应用内窗口
+        register_action(hkm, "close_tab", &config.close_tab, move || {
+            send_keys(&[VK_CONTROL, VK_W]);
+        });
+
+        // 创建应用内窗口
+        register_action(hkm, "new_tab", &config.new_tab, move || {
+            send_keys(&[VK_CONTROL, VK_T]);
+        });
+
+        // 上
+        register_action(hkm, "scroll_up", &config.scroll_up, move || {
+            send_keys(&[VK_UP]);
+        });
+
+        // 下
+        register_action(hkm, "scroll_down", &config.scroll_down, move || {
+            send_keys(&[VK_DOWN]);
+        });
+
+        // 切换左标题页
+        register_action(hkm, "prev_tab", &config.prev_tab, move || {
+            send_keys(&[VK_CONTROL, VK_SHIFT, VK_TAB, VK_A]);
+        });
+
+        // 切换右标题页
+        register_action(hkm, "next_tab", &config.next_tab, move || {
+            send_keys(&[VK_CONTROL, VK_TAB, VK_D]);
+        });
+
+        // 刷新
+        register_action(hkm, "refresh", &config.refresh, move || {
+            send_keys(&[VK_F5]);
+        });
+
+        // Vim 风格的计数前缀与多键命令（5j、5k、gg、gl、gh ...）
+        register_parser_key(hkm, VKey::J, &[], &vim_parser, |p| p.feed_key('j'));
+        register_parser_key(hkm, VKey::K, &[], &vim_parser, |p| p.feed_key('k'));
+        register_parser_key(hkm, VKey::G, &[], &vim_parser, |p| p.feed_key('g'));
+        register_parser_key(hkm, VKey::L, &[], &vim_parser, |p| p.feed_key('l'));
+        register_parser_key(hkm, VKey::H, &[], &vim_parser, |p| p.feed_key('h'));
+        register_parser_key(hkm, VKey::Escape, &[], &vim_parser, |p| p.reset());
+        for (vkey, digit) in [
+            (VKey::Key0, 0),
+            (VKey::Key1, 1),
+            (VKey::Key2, 2),
+            (VKey::Key3, 3),
+            (VKey::Key4, 4),
+            (VKey::Key5, 5),
+            (VKey::Key6, 6),
+            (VKey::Key7, 7),
+            (VKey::Key8, 8),
+            (VKey::Key9, 9),
+        ] {
+            register_parser_key(hkm, vkey, &[], &vim_parser, move |p| p.feed_digit(digit));
+        }
 
-    // 暂停/启动
-    hkm.register_pause_hotkey(VKey::F23, &[VKey::LWin, VKey::Shift], move || {
-        show_state.store(!show_state.load(Ordering::Relaxed), Ordering::Relaxed);
-        event_loop_proxy.send_event(()).unwrap();
+        // 暂停/启动
+        match parse_accelerator(&config.toggle) {
+            Ok((modifiers, main_key)) => {
+                hkm.register_pause_hotkey(main_key, &modifiers, move || {
+                    show_state.store(!show_state.load(Ordering::Relaxed), Ordering::Relaxed);
+                    event_loop_proxy.send_event(()).unwrap();
+                })
+                .unwrap();
+            }
+            Err(e) => {
+                eprintln!(
+                    "VimBrowse: invalid accelerator for \"toggle\" ({:?}): {e}",
+                    config.toggle
+                );
+            }
+        }
     })
-    .unwrap();
-
-    hkm.event_loop();
-}
\ No newline at end of file
+    .expect("failed to start the hotkey hook supervisor");
+}
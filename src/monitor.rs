@@ -1,19 +1,64 @@
 use anyhow::Result;
-use windows::Win32::{
-    Foundation::POINT,
-    Graphics::Gdi::{GetMonitorInfoW, MONITOR_DEFAULTTOPRIMARY, MONITORINFO, MonitorFromPoint},
+use windows::Win32::Foundation::{BOOL, LPARAM, RECT, TRUE};
+use windows::Win32::Graphics::Gdi::{
+    EnumDisplayMonitors, GetMonitorInfoW, HDC, HMONITOR, MONITORINFO,
 };
+use windows::Win32::UI::HiDpi::{GetDpiForMonitor, MDT_EFFECTIVE_DPI};
 
-pub fn get_primary_monitor_logical_size() -> Result<(f64, f64)> {
+/// Geometry and scaling of a single physical monitor, as reported by the
+/// Windows desktop window manager.
+pub struct MonitorInfo {
+    pub position: (i32, i32),
+    pub size: (u32, u32),
+    pub scale_factor: f64,
+}
+
+unsafe extern "system" fn enum_monitor_proc(
+    hmonitor: HMONITOR,
+    _hdc: HDC,
+    _rect: *mut RECT,
+    lparam: LPARAM,
+) -> BOOL {
     unsafe {
+        let monitors = &mut *(lparam.0 as *mut Vec<MonitorInfo>);
+
         let mut info: MONITORINFO = std::mem::zeroed();
         info.cbSize = std::mem::size_of::<MONITORINFO>() as u32;
-        let monitor = MonitorFromPoint(POINT { x: 0, y: 0 }, MONITOR_DEFAULTTOPRIMARY);
-        GetMonitorInfoW(monitor, &mut info).ok()?;
+        if GetMonitorInfoW(hmonitor, &mut info).as_bool() {
+            let mut dpi_x = 96u32;
+            let mut dpi_y = 96u32;
+            let _ = GetDpiForMonitor(hmonitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y);
 
-        Ok((
-            (info.rcMonitor.right - info.rcMonitor.left) as f64,
-            (info.rcMonitor.bottom - info.rcMonitor.top) as f64,
-        ))
+            monitors.push(MonitorInfo {
+                position: (info.rcMonitor.left, info.rcMonitor.top),
+                size: (
+                    (info.rcMonitor.right - info.rcMonitor.left) as u32,
+                    (info.rcMonitor.bottom - info.rcMonitor.top) as u32,
+                ),
+                scale_factor: dpi_x as f64 / 96.0,
+            });
+        }
+
+        TRUE
     }
 }
+
+/// Enumerate every active display, mirroring the `EnumDisplayMonitors`
+/// walk used by other windowing libraries to build their monitor list.
+pub fn enumerate_monitors() -> Result<Vec<MonitorInfo>> {
+    let mut monitors: Vec<MonitorInfo> = Vec::new();
+
+    unsafe {
+        EnumDisplayMonitors(
+            None,
+            None,
+            Some(enum_monitor_proc),
+            LPARAM(&mut monitors as *mut _ as isize),
+        )
+        .ok()?;
+    }
+
+    anyhow::ensure!(!monitors.is_empty(), "no monitors found");
+
+    Ok(monitors)
+}